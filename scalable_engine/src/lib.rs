@@ -1,4 +1,8 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
 use tokio::runtime::{Builder, Runtime};
+use tokio::task::{AbortHandle, Id, JoinError, JoinSet, LocalSet};
 use futures::future::join_all;
 
 /// Engine provides a wrapper around a Tokio runtime capable of executing
@@ -10,13 +14,11 @@ pub struct Engine {
 impl Engine {
     /// Create a new engine with the given number of worker threads.
     pub fn new(worker_threads: usize) -> Self {
-        let runtime = Builder::new_multi_thread()
+        EngineBuilder::new()
             .worker_threads(worker_threads)
-            .enable_all()
+            .enable_io(true)
+            .enable_time(true)
             .build()
-            .expect("failed to build runtime");
-
-        Self { runtime }
     }
 
     /// Create a new engine sized to the available CPU count.
@@ -42,6 +44,439 @@ impl Engine {
     {
         self.runtime.spawn(future)
     }
+
+    /// Block the calling thread until `fut` resolves, driving it on the
+    /// engine's runtime.
+    ///
+    /// Panics if called from a thread already owned by a Tokio runtime (for
+    /// example, from inside one of the engine's own tasks). Prefer
+    /// [`Engine::block_on_checked`] when that cannot be ruled out, or
+    /// [`Engine::spawn_blocking`] to move synchronous work off the async
+    /// worker threads instead.
+    pub fn block_on<F: std::future::Future>(&self, fut: F) -> F::Output {
+        self.runtime.block_on(fut)
+    }
+
+    /// Like [`Engine::block_on`], but returns a [`NestedRuntimeError`]
+    /// instead of panicking if called from a thread already owned by a
+    /// Tokio runtime.
+    ///
+    /// This guards against the well-known "calling async from sync context"
+    /// footgun: code embedding an `Engine` inside a synchronous trait
+    /// implementation can hit this if that implementation is itself invoked
+    /// from within the runtime. Callers that detect this should off-load the
+    /// work with [`Engine::spawn_blocking`] instead.
+    pub fn block_on_checked<F: std::future::Future>(
+        &self,
+        fut: F,
+    ) -> Result<F::Output, NestedRuntimeError> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(NestedRuntimeError);
+        }
+        Ok(self.runtime.block_on(fut))
+    }
+
+    /// Run a blocking (CPU-bound or synchronous) closure on the runtime's
+    /// blocking thread pool, off the async worker threads.
+    pub fn spawn_blocking<F, T>(&self, f: F) -> tokio::task::JoinHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.runtime.handle().spawn_blocking(f)
+    }
+
+    /// Run multiple async tasks and collect their results in the order they
+    /// complete rather than the order they were submitted.
+    ///
+    /// Unlike [`Engine::run_tasks`], a single slow task does not hold up the
+    /// results of the others. Panicked or cancelled tasks are reported as
+    /// `Err` instead of unwinding the caller.
+    pub fn run_tasks_as_completed<F, T>(&self, tasks: Vec<F>) -> Vec<Result<T, JoinError>>
+    where
+        F: std::future::Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.runtime.block_on(async {
+            let mut stream = self.spawn_as_completed(tasks);
+            let mut results = Vec::new();
+            while let Some(result) = stream.join_next().await {
+                results.push(result);
+            }
+            results
+        })
+    }
+
+    /// Spawn multiple tasks onto the engine and return a [`TaskStream`] that
+    /// yields each result as soon as it completes.
+    ///
+    /// Dropping the returned stream aborts any tasks that are still running,
+    /// so orphaned work is cleaned up automatically.
+    pub fn spawn_as_completed<F, T>(&self, tasks: Vec<F>) -> TaskStream<T>
+    where
+        F: std::future::Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut set = JoinSet::new();
+        for task in tasks {
+            set.spawn_on(task, self.runtime.handle());
+        }
+        TaskStream { set }
+    }
+
+    /// Run multiple keyed tasks concurrently and return a [`KeyedTaskStream`]
+    /// that yields each task's key alongside its result as it completes.
+    ///
+    /// This is useful when callers need to know which input produced which
+    /// output, or want to cancel a specific in-flight task by key.
+    ///
+    /// Matching `tokio_util::task::JoinMap`, keys need not be unique: if two
+    /// tasks share a key, the later one replaces the earlier one, aborting
+    /// the earlier task so it cannot leak as an un-abortable, still-tracked
+    /// entry.
+    pub fn run_keyed_tasks<K, F, T>(&self, tasks: Vec<(K, F)>) -> KeyedTaskStream<K, T>
+    where
+        K: Hash + Eq + Clone,
+        F: std::future::Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut set = JoinSet::new();
+        let mut keys_by_id = HashMap::new();
+        let mut handles_by_key = HashMap::new();
+        for (key, task) in tasks {
+            let abort_handle = set.spawn_on(task, self.runtime.handle());
+            keys_by_id.insert(abort_handle.id(), key.clone());
+            if let Some(previous) = handles_by_key.insert(key, abort_handle) {
+                keys_by_id.remove(&previous.id());
+                previous.abort();
+            }
+        }
+        KeyedTaskStream {
+            set,
+            keys_by_id,
+            handles_by_key,
+        }
+    }
+
+    /// Run multiple async tasks, bounding each to at most `duration` before it
+    /// is cancelled.
+    ///
+    /// Each task is spawned onto the runtime (so, as with
+    /// [`Engine::run_tasks_as_completed`] and [`Engine::run_keyed_tasks`], a
+    /// multi-thread engine actually runs them across its worker pool rather
+    /// than cooperatively on one thread) and wrapped in
+    /// [`tokio::time::timeout`]; a task that exceeds the deadline is aborted
+    /// at its next `.await` point and reported as `Ok(Err(Elapsed))` rather
+    /// than holding up the rest of the batch. A task that panics or is
+    /// cancelled is reported as `Err(JoinError)`, consistent with
+    /// [`Engine::run_tasks_as_completed`] and [`Engine::run_keyed_tasks`] — it
+    /// does not take down the rest of the batch either. Results are returned
+    /// in submission order.
+    pub fn run_tasks_timeout<F, T>(
+        &self,
+        tasks: Vec<F>,
+        duration: std::time::Duration,
+    ) -> Vec<Result<Result<T, tokio::time::error::Elapsed>, JoinError>>
+    where
+        F: std::future::Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut set = JoinSet::new();
+        let mut indices = HashMap::new();
+        for (index, task) in tasks.into_iter().enumerate() {
+            let handle = set.spawn_on(
+                async move { tokio::time::timeout(duration, task).await },
+                self.runtime.handle(),
+            );
+            indices.insert(handle.id(), index);
+        }
+
+        self.runtime.block_on(async {
+            let mut results: Vec<Option<Result<Result<T, tokio::time::error::Elapsed>, JoinError>>> =
+                (0..indices.len()).map(|_| None).collect();
+            while let Some(join_result) = set.join_next_with_id().await {
+                let (id, result) = match join_result {
+                    Ok((id, value)) => (id, Ok(value)),
+                    Err(err) => {
+                        let id = err.id();
+                        (id, Err(err))
+                    }
+                };
+                results[indices[&id]] = Some(result);
+            }
+            results
+                .into_iter()
+                .map(|result| result.expect("every spawned task completes exactly once"))
+                .collect()
+        })
+    }
+
+    /// Consume the engine, giving in-flight tasks up to `duration` to finish
+    /// before forcibly aborting them and shutting down the runtime.
+    ///
+    /// This is the engine's counterpart to [`tokio::runtime::Runtime::shutdown_timeout`],
+    /// for callers that need a bounded grace period before process exit.
+    pub fn shutdown_timeout(self, duration: std::time::Duration) {
+        self.runtime.shutdown_timeout(duration);
+    }
+}
+
+/// A single-threaded engine paired with a [`LocalSet`], for `!Send` futures
+/// (for example, ones built on `Rc`) that cannot run on a multi-thread
+/// [`Engine`].
+///
+/// Unlike `Engine`, `LocalEngine` is `!Send`: a `LocalSet` may only ever be
+/// driven, and dropped, by the thread that owns it, so the type system
+/// itself rules out moving one across threads rather than relying on a
+/// runtime check. This mirrors Tokio's own split between the thread-pool and
+/// current-thread executors.
+pub struct LocalEngine {
+    runtime: Runtime,
+    local_set: LocalSet,
+}
+
+impl LocalEngine {
+    /// Create a new single-threaded engine.
+    pub fn new() -> Self {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build runtime");
+
+        Self {
+            runtime,
+            local_set: LocalSet::new(),
+        }
+    }
+
+    /// Spawn a single `!Send` future onto the engine's [`LocalSet`].
+    pub fn spawn_local<F, T>(&self, future: F) -> tokio::task::JoinHandle<T>
+    where
+        F: std::future::Future<Output = T> + 'static,
+        T: 'static,
+    {
+        self.local_set.spawn_local(future)
+    }
+
+    /// Run multiple `!Send` async tasks to completion and collect their
+    /// results in submission order.
+    pub fn run_local_tasks<F, T>(&self, tasks: Vec<F>) -> Vec<T>
+    where
+        F: std::future::Future<Output = T> + 'static,
+        T: 'static,
+    {
+        self.runtime.block_on(self.local_set.run_until(async {
+            let handles: Vec<_> = tasks
+                .into_iter()
+                .map(|task| self.local_set.spawn_local(task))
+                .collect();
+            let mut results = Vec::with_capacity(handles.len());
+            for handle in handles {
+                results.push(handle.await.expect("local task panicked"));
+            }
+            results
+        }))
+    }
+}
+
+impl Default for LocalEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error returned by [`Engine::block_on_checked`] when called from within a
+/// thread already driven by a Tokio runtime.
+#[derive(Debug)]
+pub struct NestedRuntimeError;
+
+impl std::fmt::Display for NestedRuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "block_on_checked called from within a Tokio runtime; use spawn_blocking or .await instead"
+        )
+    }
+}
+
+impl std::error::Error for NestedRuntimeError {}
+
+/// A builder for configuring an [`Engine`]'s underlying multi-thread Tokio
+/// runtime, for callers who need more than a worker count: named threads for
+/// observability, larger stacks for deep recursion, or an IO-only runtime for
+/// lighter builds.
+///
+/// Unlike `Engine::new`, the builder does not enable the IO or time drivers
+/// by default; call [`EngineBuilder::enable_io`] / [`EngineBuilder::enable_time`]
+/// for the ones your tasks need.
+pub struct EngineBuilder {
+    builder: Builder,
+}
+
+impl EngineBuilder {
+    /// Start building an engine backed by a multi-thread runtime.
+    pub fn new() -> Self {
+        Self {
+            builder: Builder::new_multi_thread(),
+        }
+    }
+
+    /// Set the number of worker threads the runtime will use.
+    pub fn worker_threads(mut self, worker_threads: usize) -> Self {
+        self.builder.worker_threads(worker_threads);
+        self
+    }
+
+    /// Set the name given to every thread the runtime spawns.
+    pub fn thread_name(mut self, name: impl Into<String>) -> Self {
+        self.builder.thread_name(name.into());
+        self
+    }
+
+    /// Set a closure used to generate each worker thread's name.
+    pub fn thread_name_fn(
+        mut self,
+        f: impl Fn() -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.builder.thread_name_fn(f);
+        self
+    }
+
+    /// Set the stack size, in bytes, for the runtime's worker threads.
+    pub fn thread_stack_size(mut self, size: usize) -> Self {
+        self.builder.thread_stack_size(size);
+        self
+    }
+
+    /// Set the maximum number of threads the runtime's blocking pool may use.
+    pub fn max_blocking_threads(mut self, max_threads: usize) -> Self {
+        self.builder.max_blocking_threads(max_threads);
+        self
+    }
+
+    /// Enable the IO driver, required for `tokio::net`/`tokio::io` types, if
+    /// `enabled` is true. Passing false leaves the current setting unchanged.
+    pub fn enable_io(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.builder.enable_io();
+        }
+        self
+    }
+
+    /// Enable the time driver, required for `tokio::time` types such as
+    /// `timeout` and `sleep`, if `enabled` is true. Passing false leaves the
+    /// current setting unchanged.
+    pub fn enable_time(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.builder.enable_time();
+        }
+        self
+    }
+
+    /// Set a callback invoked each time the runtime starts a worker thread.
+    pub fn on_thread_start(mut self, f: impl Fn() + Send + Sync + 'static) -> Self {
+        self.builder.on_thread_start(f);
+        self
+    }
+
+    /// Set a callback invoked each time the runtime stops a worker thread.
+    pub fn on_thread_stop(mut self, f: impl Fn() + Send + Sync + 'static) -> Self {
+        self.builder.on_thread_stop(f);
+        self
+    }
+
+    /// Build the configured [`Engine`].
+    pub fn build(mut self) -> Engine {
+        let runtime = self.builder.build().expect("failed to build runtime");
+        Engine { runtime }
+    }
+}
+
+impl Default for EngineBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A handle to a batch of tasks spawned via [`Engine::spawn_as_completed`].
+///
+/// Results are yielded in completion order via [`TaskStream::join_next`].
+/// Dropping the stream aborts any tasks that have not yet finished.
+pub struct TaskStream<T> {
+    set: JoinSet<T>,
+}
+
+impl<T: 'static> TaskStream<T> {
+    /// Wait for the next task to complete, in completion order.
+    ///
+    /// Returns `None` once every task has finished.
+    pub async fn join_next(&mut self) -> Option<Result<T, JoinError>> {
+        self.set.join_next().await
+    }
+}
+
+/// A handle to a batch of keyed tasks spawned via [`Engine::run_keyed_tasks`].
+///
+/// Modeled on `tokio_util::task::JoinMap`: each result is paired with the key
+/// its task was submitted under, and a specific task can be cancelled by key.
+/// Dropping the stream aborts every task that has not yet finished.
+pub struct KeyedTaskStream<K, T> {
+    set: JoinSet<T>,
+    keys_by_id: HashMap<Id, K>,
+    handles_by_key: HashMap<K, AbortHandle>,
+}
+
+impl<K, T> KeyedTaskStream<K, T>
+where
+    K: Hash + Eq + Clone,
+    T: 'static,
+{
+    /// Wait for the next task to complete, in completion order, returning its
+    /// key alongside the result.
+    ///
+    /// Returns `None` once every task has finished or been aborted.
+    pub async fn join_next(&mut self) -> Option<(K, Result<T, JoinError>)> {
+        loop {
+            let (id, result) = match self.set.join_next_with_id().await? {
+                Ok((id, value)) => (id, Ok(value)),
+                Err(err) => (err.id(), Err(err)),
+            };
+            // A task whose key was removed by `abort`, or that was replaced by
+            // a later task under the same key, still surfaces here once it
+            // stops; it carries no useful key, so skip it rather than
+            // treating the whole stream as exhausted.
+            let Some(key) = self.keys_by_id.remove(&id) else {
+                continue;
+            };
+            self.handles_by_key.remove(&key);
+            return Some((key, result));
+        }
+    }
+
+    /// Abort the in-flight task submitted under `key`, if it is still running.
+    ///
+    /// Returns `true` if a task was found and aborted. A subsequent call to
+    /// [`KeyedTaskStream::join_next`] will not yield this key.
+    pub fn abort(&mut self, key: &K) -> bool {
+        match self.handles_by_key.remove(key) {
+            Some(handle) => {
+                self.keys_by_id.remove(&handle.id());
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The number of tasks that are still in flight.
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Whether there are no tasks left in flight.
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -57,4 +492,175 @@ mod tests {
         let results = engine.run_tasks(tasks);
         assert_eq!(results, (1..=10).collect::<Vec<_>>());
     }
+
+    #[test]
+    fn runs_tasks_as_completed() {
+        let engine = Engine::new(4);
+        let tasks = (0..10)
+            .map(|i| async move { i + 1 })
+            .collect::<Vec<_>>();
+        let mut results = engine
+            .run_tasks_as_completed(tasks)
+            .into_iter()
+            .map(|r| r.expect("task panicked"))
+            .collect::<Vec<_>>();
+        results.sort_unstable();
+        assert_eq!(results, (1..=10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn runs_keyed_tasks() {
+        let engine = Engine::new(4);
+        let tasks = (0..10)
+            .map(|i| (i, async move { i + 1 }))
+            .collect::<Vec<_>>();
+        let mut stream = engine.run_keyed_tasks(tasks);
+
+        let mut results = HashMap::new();
+        while let Some((key, result)) = engine.runtime.block_on(stream.join_next()) {
+            results.insert(key, result.expect("task panicked"));
+        }
+
+        for i in 0..10 {
+            assert_eq!(results[&i], i + 1);
+        }
+    }
+
+    #[test]
+    fn run_keyed_tasks_replaces_duplicate_key() {
+        let engine = Engine::new(4);
+        let tasks = vec![
+            (
+                "dup",
+                Box::pin(async {
+                    tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                    1
+                }) as std::pin::Pin<Box<dyn std::future::Future<Output = i32> + Send>>,
+            ),
+            ("dup", Box::pin(async { 2 })),
+        ];
+        let mut stream = engine.run_keyed_tasks(tasks);
+
+        let (key, result) = engine.runtime.block_on(stream.join_next()).unwrap();
+        assert_eq!(key, "dup");
+        assert_eq!(result.unwrap(), 2);
+        assert!(engine.runtime.block_on(stream.join_next()).is_none());
+
+        // The earlier "dup" task was aborted on insert, so it cannot be
+        // un-abortably left behind.
+        assert!(!stream.abort(&"dup"));
+    }
+
+    #[test]
+    fn aborts_keyed_task_by_key() {
+        let engine = Engine::new(4);
+        let tasks = vec![
+            (
+                "slow",
+                Box::pin(async {
+                    tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                    1
+                }) as std::pin::Pin<Box<dyn std::future::Future<Output = i32> + Send>>,
+            ),
+            ("fast", Box::pin(async { 2 })),
+        ];
+        let mut stream = engine.run_keyed_tasks(tasks);
+
+        assert!(stream.abort(&"slow"));
+        assert!(!stream.abort(&"slow"));
+
+        let (key, result) = engine.runtime.block_on(stream.join_next()).unwrap();
+        assert_eq!(key, "fast");
+        assert_eq!(result.unwrap(), 2);
+        assert!(engine.runtime.block_on(stream.join_next()).is_none());
+    }
+
+    #[test]
+    fn runs_local_tasks_with_non_send_values() {
+        let engine = LocalEngine::new();
+        let tasks = (0..10)
+            .map(|i| async move {
+                let value = std::rc::Rc::new(i);
+                *value + 1
+            })
+            .collect::<Vec<_>>();
+        let results = engine.run_local_tasks(tasks);
+        assert_eq!(results, (1..=10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn engine_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Engine>();
+    }
+
+    #[test]
+    fn builder_configures_engine() {
+        let engine = EngineBuilder::new()
+            .worker_threads(2)
+            .thread_name("engine-worker")
+            .enable_io(true)
+            .enable_time(true)
+            .build();
+        let tasks = (0..10)
+            .map(|i| async move { i + 1 })
+            .collect::<Vec<_>>();
+        let results = engine.run_tasks(tasks);
+        assert_eq!(results, (1..=10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn block_on_checked_detects_nested_runtime() {
+        let engine = Engine::new(2);
+        let result = engine.block_on(async { engine.block_on_checked(async { 1 }) });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn block_on_checked_runs_outside_runtime() {
+        let engine = Engine::new(2);
+        let result = engine.block_on_checked(async { 42 });
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[test]
+    fn spawn_blocking_offloads_sync_work() {
+        let engine = Engine::new(2);
+        let handle = engine.spawn_blocking(|| 1 + 1);
+        let result = engine.block_on(handle).unwrap();
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn run_tasks_timeout_reports_elapsed_tasks() {
+        let engine = Engine::new(2);
+        let tasks: Vec<std::pin::Pin<Box<dyn std::future::Future<Output = i32> + Send>>> = vec![
+            Box::pin(async { 1 }),
+            Box::pin(async {
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                2
+            }),
+        ];
+        let results = engine.run_tasks_timeout(tasks, std::time::Duration::from_millis(50));
+        assert_eq!(results[0].as_ref().unwrap().as_ref().unwrap(), &1);
+        assert!(results[1].as_ref().unwrap().is_err());
+    }
+
+    #[test]
+    fn run_tasks_timeout_reports_panics_without_losing_other_results() {
+        let engine = Engine::new(2);
+        let tasks: Vec<std::pin::Pin<Box<dyn std::future::Future<Output = i32> + Send>>> = vec![
+            Box::pin(async { 1 }),
+            Box::pin(async { panic!("boom") }),
+        ];
+        let results = engine.run_tasks_timeout(tasks, std::time::Duration::from_millis(50));
+        assert_eq!(results[0].as_ref().unwrap().as_ref().unwrap(), &1);
+        assert!(results[1].is_err());
+    }
+
+    #[test]
+    fn shutdown_timeout_drains_runtime() {
+        let engine = Engine::new(2);
+        engine.shutdown_timeout(std::time::Duration::from_millis(100));
+    }
 }